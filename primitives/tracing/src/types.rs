@@ -26,7 +26,7 @@ use sp_std::Writer;
 use codec::{Encode, Decode};
 
 /// The Tracing Level – the user can filter by this
-#[derive(Clone, Encode, Decode, Debug)]
+#[derive(Clone, Encode, Decode, Debug, PartialEq, Eq)]
 pub enum WasmLevel {
 	/// This is a fatal errors
 	ERROR,
@@ -40,6 +40,119 @@ pub enum WasmLevel {
 	TRACE
 }
 
+impl WasmLevel {
+	/// The severity of this level, lower is more severe – mirrors the
+	/// ordering `tracing` itself uses (`ERROR` is the highest priority).
+	fn severity(&self) -> u8 {
+		match self {
+			WasmLevel::ERROR => 0,
+			WasmLevel::WARN => 1,
+			WasmLevel::INFO => 2,
+			WasmLevel::DEBUG => 3,
+			WasmLevel::TRACE => 4,
+		}
+	}
+
+	/// Parse a single level name, case-insensitively. Returns `None` if
+	/// `s` isn't one of the known level names.
+	fn parse(s: &str) -> Option<Self> {
+		Some(match s.trim() {
+			s if s.eq_ignore_ascii_case("error") => WasmLevel::ERROR,
+			s if s.eq_ignore_ascii_case("warn") => WasmLevel::WARN,
+			s if s.eq_ignore_ascii_case("info") => WasmLevel::INFO,
+			s if s.eq_ignore_ascii_case("debug") => WasmLevel::DEBUG,
+			s if s.eq_ignore_ascii_case("trace") => WasmLevel::TRACE,
+			_ => return None,
+		})
+	}
+}
+
+/// A single parsed directive, matching the `target=level` (or bare `level`
+/// for the default) grammar also used by `tracing-subscriber`'s `EnvFilter`.
+#[derive(Clone, Debug)]
+struct WasmFilterDirective {
+	/// Always `Some(prefix)` as built by `WasmFilter::from_str`; a bare
+	/// default level is kept in `WasmFilter::default`, not as a directive.
+	target: Option<Vec<u8>>,
+	level: WasmLevel,
+}
+
+/// An `EnvFilter`-style directive filter, evaluated against `WasmMetadata`
+/// so that disabled span/event entries can be dropped before they are
+/// SCALE-encoded and sent across the wasm boundary.
+///
+/// Parses directive strings such as `"runtime=debug,sp_io=trace,info"`: a
+/// comma-separated list of `target=level` directives plus an optional bare
+/// `level` that is used as the default when no target-specific directive
+/// matches.
+#[derive(Clone, Debug, Default)]
+pub struct WasmFilter {
+	directives: Vec<WasmFilterDirective>,
+	default: Option<WasmLevel>,
+}
+
+impl WasmFilter {
+	/// Parse `filter` into a `WasmFilter`. Directives that don't parse
+	/// (unknown level name, empty target) are silently skipped.
+	pub fn from_str(filter: &str) -> Self {
+		let mut directives = Vec::new();
+		let mut default = None;
+
+		for directive in filter.split(',') {
+			let directive = directive.trim();
+			if directive.is_empty() {
+				continue
+			}
+
+			match directive.find('=') {
+				Some(pos) => {
+					let target = &directive[..pos];
+					let level = &directive[pos + 1..];
+					if target.is_empty() {
+						continue
+					}
+					if let Some(level) = WasmLevel::parse(level) {
+						directives.push(WasmFilterDirective {
+							target: Some(target.as_bytes().to_vec()),
+							level,
+						});
+					}
+				},
+				None => {
+					if let Some(level) = WasmLevel::parse(directive) {
+						default = Some(level);
+					}
+				},
+			}
+		}
+
+		WasmFilter { directives, default }
+	}
+
+	/// Whether the given `metadata` is enabled under this filter: the
+	/// directive whose target is the longest prefix of `metadata.target`
+	/// applies (falling back to the default directive, or to enabled if
+	/// no default was given), and `metadata.level` must be at or above
+	/// that directive's level.
+	pub fn is_enabled(&self, metadata: &WasmMetadata) -> bool {
+		let target = metadata.target.as_slice();
+
+		let matched = self.directives.iter()
+			.filter(|d| d.target.as_ref().map_or(false, |t| target.starts_with(t.as_slice())))
+			.max_by_key(|d| d.target.as_ref().map_or(0, |t| t.len()));
+
+		let level = match matched {
+			Some(d) => &d.level,
+			None => match &self.default {
+				Some(l) => l,
+				None => return true,
+			},
+		};
+
+		metadata.level.severity() <= level.severity()
+	}
+}
+
 /// A paramter value provided to the span/event
 #[derive(Encode, Decode, Clone, Debug)]
 pub enum WasmValue {
@@ -58,6 +171,43 @@ pub enum WasmValue {
 	Encoded(Vec<u8>),
 }
 
+#[cfg(feature = "std")]
+impl WasmValue {
+	/// Render this value as a natural `serde_json::Value`: numbers/bools
+	/// map directly, `Str`/`Formatted` become UTF-8 strings, and the opaque
+	/// `Encoded` SCALE blob is expanded via `field_name`'s registered
+	/// `EncodedValueDecoder`, falling back to `{ "scale_hex": "0x.." }` so
+	/// it survives the round-trip even with no decoder registered.
+	fn to_json(&self, field_name: &str) -> serde_json::Value {
+		match self {
+			WasmValue::U8(v) => serde_json::json!(v),
+			WasmValue::I8(v) => serde_json::json!(v),
+			WasmValue::U32(v) => serde_json::json!(v),
+			WasmValue::I32(v) => serde_json::json!(v),
+			WasmValue::I64(v) => serde_json::json!(v),
+			WasmValue::U64(v) => serde_json::json!(v),
+			WasmValue::Bool(v) => serde_json::json!(v),
+			WasmValue::Str(v) | WasmValue::Formatted(v) =>
+				serde_json::json!(std::string::String::from_utf8_lossy(v)),
+			WasmValue::Encoded(v) => match std_features::decode_encoded_value(field_name, v) {
+				Some(decoded) => serde_json::json!(decoded),
+				None => serde_json::json!({ "scale_hex": to_hex(v) }),
+			},
+		}
+	}
+}
+
+/// Render `bytes` as a `0x`-prefixed lowercase hex string.
+#[cfg(feature = "std")]
+fn to_hex(bytes: &[u8]) -> std::string::String {
+	let mut s = std::string::String::with_capacity(2 + bytes.len() * 2);
+	s.push_str("0x");
+	for b in bytes {
+		s.push_str(&std::format!("{:02x}", b));
+	}
+	s
+}
+
 impl From<u8> for WasmValue {
 	fn from(u: u8) -> WasmValue {
 		WasmValue::U8(u)
@@ -217,6 +367,28 @@ impl WasmValuesSet {
 	}
 }
 
+#[cfg(feature = "std")]
+impl WasmValuesSet {
+	/// Render this set of fields as a JSON object, keyed by field name.
+	/// Fields with no value serialize as JSON `null`.
+	fn to_json_value(&self) -> serde_json::Value {
+		let fields = self.0.iter()
+			.map(|(name, value)| {
+				let name = std::string::String::from_utf8_lossy(&name.0).into_owned();
+				let value = value.as_ref()
+					.map_or(serde_json::Value::Null, |v| v.to_json(&name));
+				(name, value)
+			})
+			.collect::<serde_json::Map<_, _>>();
+		serde_json::Value::Object(fields)
+	}
+
+	/// Render this set of fields as a JSON string.
+	pub fn to_json(&self) -> std::string::String {
+		self.to_json_value().to_string()
+	}
+}
+
 /// Metadata provides generic information about the specifc location of the
 /// `span!` or `event!` call on the wasm-side.
 #[derive(Encode, Decode, Clone, Debug)]
@@ -248,6 +420,32 @@ pub struct WasmEntryAttributes {
 	pub metadata: WasmMetadata,
 	/// the Values provided
 	pub fields: WasmValuesSet,
+	/// ids of spans that causally preceded this one, but are not its
+	/// parent – e.g. a deferred task span triggered by an earlier
+	/// extrinsic span. Every id is passed on to the subscriber unchecked;
+	/// the host does not drop ids the subscriber no longer knows about,
+	/// and how those are handled is up to the subscriber.
+	pub follows_from: Vec<u64>,
+}
+
+#[cfg(feature = "std")]
+impl WasmEntryAttributes {
+	/// Render this entry as a single structured JSON object, carrying the
+	/// metadata plus a nested `fields` object, so external log collectors
+	/// can consume it without re-implementing the walk over `WasmValue`.
+	pub fn to_json(&self) -> std::string::String {
+		let metadata = &self.metadata;
+		serde_json::json!({
+			"name": std::string::String::from_utf8_lossy(&metadata.name),
+			"target": std::string::String::from_utf8_lossy(&metadata.target),
+			"level": std::format!("{:?}", metadata.level),
+			"file": std::string::String::from_utf8_lossy(&metadata.file),
+			"line": metadata.line,
+			"module_path": std::string::String::from_utf8_lossy(&metadata.module_path),
+			"is_span": metadata.is_span,
+			"fields": self.fields.to_json_value(),
+		}).to_string()
+	}
 }
 
 #[cfg(feature = "std")]
@@ -255,14 +453,126 @@ mod std_features {
 
 	use tracing_core::callsite;
 	use tracing;
+	use std::sync::RwLock;
+
+	lazy_static::lazy_static! {
+		/// The currently configured `WasmFilter`. Defaults to enabling
+		/// everything, so existing behaviour is unchanged until an operator
+		/// opts in via `set_wasm_filter`.
+		static ref WASM_FILTER: RwLock<crate::WasmFilter> = RwLock::new(crate::WasmFilter::default());
+	}
+
+	/// Configure the directive filter consulted by `WasmEntryAttributes::emit`
+	/// and the `Into<tracing::Span>` conversion, letting operators silence
+	/// noisy runtime targets without recompiling the wasm blob.
+	pub fn set_wasm_filter(filter: crate::WasmFilter) {
+		*WASM_FILTER.write().expect("WASM_FILTER lock poisoned") = filter;
+	}
+
+	/// Decodes a `WasmValue::Encoded` SCALE blob into a human/structured
+	/// representation. Registered per field name via
+	/// `register_encoded_value_decoder`.
+	pub type EncodedValueDecoder = fn(&[u8]) -> Option<std::string::String>;
+
+	lazy_static::lazy_static! {
+		/// Decoders for `WasmValue::Encoded` fields, keyed by field name.
+		/// Fields with no registered decoder fall back to hex when rendered.
+		static ref ENCODED_VALUE_DECODERS: RwLock<std::collections::HashMap<std::string::String, EncodedValueDecoder>> =
+			RwLock::new(std::collections::HashMap::new());
+	}
+
+	/// Register a decoder for `WasmValue::Encoded` fields named
+	/// `field_name`, so node operators can expand runtime-specific SCALE
+	/// types (e.g. `AccountId`, `Balance`) into something readable when a
+	/// `WasmValuesSet` is rendered or forwarded, instead of opaque hex.
+	pub fn register_encoded_value_decoder(field_name: &str, decoder: EncodedValueDecoder) {
+		ENCODED_VALUE_DECODERS.write().expect("ENCODED_VALUE_DECODERS lock poisoned")
+			.insert(field_name.to_string(), decoder);
+	}
+
+	/// Decode `bytes` for field `field_name` with its registered decoder,
+	/// if any.
+	pub(crate) fn decode_encoded_value(field_name: &str, bytes: &[u8]) -> Option<std::string::String> {
+		ENCODED_VALUE_DECODERS.read().expect("ENCODED_VALUE_DECODERS lock poisoned")
+			.get(field_name)
+			.and_then(|decoder| decoder(bytes))
+	}
+
+	use std::sync::atomic::{AtomicU8, Ordering};
+
+	/// `Interest::never()` cached – this callsite is disabled everywhere.
+	const INTEREST_NEVER: u8 = 0;
+	/// `Interest::sometimes()` cached – whether this callsite is enabled
+	/// depends on the current span/event, so the subscriber must be asked.
+	const INTEREST_SOMETIMES: u8 = 1;
+	/// `Interest::always()` cached – this callsite is enabled everywhere.
+	const INTEREST_ALWAYS: u8 = 2;
+
+	/// A real `Callsite` for one of the static wasm-originated `Metadata`
+	/// entries. `tracing` calls `set_interest` once per subscriber
+	/// (re)build; we cache the result in an atomic so that `is_enabled`
+	/// is a load, not a fresh query of the active subscriber, letting the
+	/// host skip decoding the SCALE buffer for disabled entries entirely.
+	pub struct WasmCallsite {
+		metadata: &'static tracing_core::Metadata<'static>,
+		interest: AtomicU8,
+	}
+
+	impl WasmCallsite {
+		const fn new(metadata: &'static tracing_core::Metadata<'static>) -> Self {
+			WasmCallsite { metadata, interest: AtomicU8::new(INTEREST_SOMETIMES) }
+		}
+
+		/// Whether this callsite is enabled, based on the cached `Interest`.
+		/// Falls back to asking the current dispatcher only for the
+		/// `sometimes` case, which `tracing` itself can't pre-compute.
+		fn is_enabled_cached(&self) -> bool {
+			match self.interest.load(Ordering::Relaxed) {
+				INTEREST_NEVER => false,
+				INTEREST_ALWAYS => true,
+				_ => tracing_core::dispatcher::get_default(|dispatch| dispatch.enabled(self.metadata)),
+			}
+		}
+	}
 
-	/// Static entry use for wasm-originated metadata.
-	pub struct WasmCallsite;
 	impl callsite::Callsite for WasmCallsite {
-		fn set_interest(&self, _: tracing_core::Interest) { unimplemented!() }
-		fn metadata(&self) -> &tracing_core::Metadata { unimplemented!() }
+		fn set_interest(&self, interest: tracing_core::Interest) {
+			let state = if interest.is_never() {
+				INTEREST_NEVER
+			} else if interest.is_always() {
+				INTEREST_ALWAYS
+			} else {
+				INTEREST_SOMETIMES
+			};
+			self.interest.store(state, Ordering::Relaxed);
+		}
+
+		fn metadata(&self) -> &tracing_core::Metadata {
+			self.metadata
+		}
+	}
+
+	/// Ensure every static wasm callsite has been registered with
+	/// `tracing_core`'s global registry. Registering also triggers an
+	/// initial `set_interest` call for the current subscriber, and the
+	/// registry re-evaluates interest for every registered callsite
+	/// whenever the subscriber is rebuilt.
+	fn register_callsites() {
+		static REGISTERED: std::sync::Once = std::sync::Once::new();
+		REGISTERED.call_once(|| {
+			callsite::register(&SPAN_ERROR_CALLSITE);
+			callsite::register(&SPAN_WARN_CALLSITE);
+			callsite::register(&SPAN_INFO_CALLSITE);
+			callsite::register(&SPAN_DEBUG_CALLSITE);
+			callsite::register(&SPAN_TRACE_CALLSITE);
+			callsite::register(&EVENT_ERROR_CALLSITE);
+			callsite::register(&EVENT_WARN_CALLSITE);
+			callsite::register(&EVENT_INFO_CALLSITE);
+			callsite::register(&EVENT_DEBUG_CALLSITE);
+			callsite::register(&EVENT_TRACE_CALLSITE);
+		});
 	}
-	static CALLSITE: WasmCallsite =  WasmCallsite;
+
 	/// The identifier we are using to inject the wasm events in the generic `tracing` system
 	pub static WASM_TRACE_IDENTIFIER: &'static str = "wasm_tracing";
 	/// The fieldname for the wasm-originated name
@@ -278,119 +588,638 @@ mod std_features {
 	// of wasm events we need these static metadata entries to inject into that system. We then provide
 	// generic `From`-implementations picking the right metadata to refer to.
 
+	static SPAN_ERROR_CALLSITE: WasmCallsite = WasmCallsite::new(&SPAN_ERROR_METADATA);
 	static SPAN_ERROR_METADATA : tracing_core::Metadata<'static> = tracing::Metadata::new(
 		WASM_TRACE_IDENTIFIER, WASM_TRACE_IDENTIFIER, tracing::Level::ERROR, None, None, None,
-		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&CALLSITE)),
+		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&SPAN_ERROR_CALLSITE)),
 		tracing_core::metadata::Kind::SPAN
 	);
 
+	static SPAN_WARN_CALLSITE: WasmCallsite = WasmCallsite::new(&SPAN_WARN_METADATA);
 	static SPAN_WARN_METADATA : tracing_core::Metadata<'static> = tracing::Metadata::new(
 		WASM_TRACE_IDENTIFIER, WASM_TRACE_IDENTIFIER, tracing::Level::WARN, None, None, None,
-		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&CALLSITE)),
+		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&SPAN_WARN_CALLSITE)),
 		tracing_core::metadata::Kind::SPAN
 	);
+
+	static SPAN_INFO_CALLSITE: WasmCallsite = WasmCallsite::new(&SPAN_INFO_METADATA);
 	static SPAN_INFO_METADATA : tracing_core::Metadata<'static> = tracing::Metadata::new(
 		WASM_TRACE_IDENTIFIER, WASM_TRACE_IDENTIFIER, tracing::Level::INFO, None, None, None,
-		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&CALLSITE)),
+		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&SPAN_INFO_CALLSITE)),
 		tracing_core::metadata::Kind::SPAN
 	);
 
+	static SPAN_DEBUG_CALLSITE: WasmCallsite = WasmCallsite::new(&SPAN_DEBUG_METADATA);
 	static SPAN_DEBUG_METADATA : tracing_core::Metadata<'static> = tracing::Metadata::new(
 		WASM_TRACE_IDENTIFIER, WASM_TRACE_IDENTIFIER, tracing::Level::DEBUG, None, None, None,
-		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&CALLSITE)),
+		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&SPAN_DEBUG_CALLSITE)),
 		tracing_core::metadata::Kind::SPAN
 	);
 
+	static SPAN_TRACE_CALLSITE: WasmCallsite = WasmCallsite::new(&SPAN_TRACE_METADATA);
 	static SPAN_TRACE_METADATA : tracing_core::Metadata<'static> = tracing::Metadata::new(
 		WASM_TRACE_IDENTIFIER, WASM_TRACE_IDENTIFIER, tracing::Level::TRACE, None, None, None,
-		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&CALLSITE)),
+		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&SPAN_TRACE_CALLSITE)),
 		tracing_core::metadata::Kind::SPAN
 	);
 
+	static EVENT_ERROR_CALLSITE: WasmCallsite = WasmCallsite::new(&EVENT_ERROR_METADATA);
 	static EVENT_ERROR_METADATA : tracing_core::Metadata<'static> = tracing::Metadata::new(
 		WASM_TRACE_IDENTIFIER, WASM_TRACE_IDENTIFIER, tracing::Level::ERROR, None, None, None,
-		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&CALLSITE)),
+		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&EVENT_ERROR_CALLSITE)),
 		tracing_core::metadata::Kind::EVENT
 	);
 
+	static EVENT_WARN_CALLSITE: WasmCallsite = WasmCallsite::new(&EVENT_WARN_METADATA);
 	static EVENT_WARN_METADATA : tracing_core::Metadata<'static> = tracing::Metadata::new(
 		WASM_TRACE_IDENTIFIER, WASM_TRACE_IDENTIFIER, tracing::Level::WARN, None, None, None,
-		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&CALLSITE)),
+		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&EVENT_WARN_CALLSITE)),
 		tracing_core::metadata::Kind::EVENT
 	);
 
+	static EVENT_INFO_CALLSITE: WasmCallsite = WasmCallsite::new(&EVENT_INFO_METADATA);
 	static EVENT_INFO_METADATA : tracing_core::Metadata<'static> = tracing::Metadata::new(
 		WASM_TRACE_IDENTIFIER, WASM_TRACE_IDENTIFIER, tracing::Level::INFO, None, None, None,
-		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&CALLSITE)),
+		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&EVENT_INFO_CALLSITE)),
 		tracing_core::metadata::Kind::EVENT
 	);
 
+	static EVENT_DEBUG_CALLSITE: WasmCallsite = WasmCallsite::new(&EVENT_DEBUG_METADATA);
 	static EVENT_DEBUG_METADATA : tracing_core::Metadata<'static> = tracing::Metadata::new(
 		WASM_TRACE_IDENTIFIER, WASM_TRACE_IDENTIFIER, tracing::Level::DEBUG, None, None, None,
-		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&CALLSITE)),
+		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&EVENT_DEBUG_CALLSITE)),
 		tracing_core::metadata::Kind::EVENT
 	);
 
+	static EVENT_TRACE_CALLSITE: WasmCallsite = WasmCallsite::new(&EVENT_TRACE_METADATA);
 	static EVENT_TRACE_METADATA : tracing_core::Metadata<'static> = tracing::Metadata::new(
 		WASM_TRACE_IDENTIFIER, WASM_TRACE_IDENTIFIER, tracing::Level::TRACE, None, None, None,
-		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&CALLSITE)),
+		tracing_core::field::FieldSet::new(GENERIC_FIELDS, tracing_core::identify_callsite!(&EVENT_TRACE_CALLSITE)),
 		tracing_core::metadata::Kind::EVENT
 	);
 
+	/// Pick the `WasmCallsite` matching the given level/kind, keyed the
+	/// same way as the `Metadata` lookup below.
+	fn callsite_for(level: &crate::WasmLevel, is_span: bool) -> &'static WasmCallsite {
+		match (level, is_span) {
+			(&crate::WasmLevel::ERROR, true) => &SPAN_ERROR_CALLSITE,
+			(&crate::WasmLevel::WARN, true) => &SPAN_WARN_CALLSITE,
+			(&crate::WasmLevel::INFO, true) => &SPAN_INFO_CALLSITE,
+			(&crate::WasmLevel::DEBUG, true) => &SPAN_DEBUG_CALLSITE,
+			(&crate::WasmLevel::TRACE, true) => &SPAN_TRACE_CALLSITE,
+			(&crate::WasmLevel::ERROR, false) => &EVENT_ERROR_CALLSITE,
+			(&crate::WasmLevel::WARN, false) => &EVENT_WARN_CALLSITE,
+			(&crate::WasmLevel::INFO, false) => &EVENT_INFO_CALLSITE,
+			(&crate::WasmLevel::DEBUG, false) => &EVENT_DEBUG_CALLSITE,
+			(&crate::WasmLevel::TRACE, false) => &EVENT_TRACE_CALLSITE,
+		}
+	}
+
+	impl WasmCallsite {
+		/// Whether a wasm-originated span or event at `level` is enabled,
+		/// based on the cached `Interest` for its callsite – cheap enough
+		/// to call before decoding the SCALE buffer that carries the
+		/// actual entry. `is_span` selects between the span and event
+		/// callsite for `level`, matching `From<&WasmMetadata>` below.
+		pub fn is_enabled(level: &crate::WasmLevel, is_span: bool) -> bool {
+			register_callsites();
+			callsite_for(level, is_span).is_enabled_cached()
+		}
+	}
+
 	impl From<&crate::WasmMetadata> for &'static tracing_core::Metadata<'static> {
 		fn from(wm: &crate::WasmMetadata) -> &'static tracing_core::Metadata<'static> {
-			match (&wm.level, wm.is_span) {
-				(&crate::WasmLevel::ERROR, true) => &SPAN_ERROR_METADATA,
-				(&crate::WasmLevel::WARN, true) => &SPAN_WARN_METADATA,
-				(&crate::WasmLevel::INFO, true) => &SPAN_INFO_METADATA,
-				(&crate::WasmLevel::DEBUG, true) => &SPAN_DEBUG_METADATA,
-				(&crate::WasmLevel::TRACE, true) => &SPAN_TRACE_METADATA,
-				(&crate::WasmLevel::ERROR, false) => &EVENT_ERROR_METADATA,
-				(&crate::WasmLevel::WARN, false) => &EVENT_WARN_METADATA,
-				(&crate::WasmLevel::INFO, false) => &EVENT_INFO_METADATA,
-				(&crate::WasmLevel::DEBUG, false) => &EVENT_DEBUG_METADATA,
-				(&crate::WasmLevel::TRACE, false) => &EVENT_TRACE_METADATA,
-			}
+			register_callsites();
+			callsite_for(&wm.level, wm.is_span).metadata
 		}
 	}
 
 	impl From<crate::WasmEntryAttributes> for tracing::Span {
 		fn from(a: crate::WasmEntryAttributes) -> tracing::Span {
+			if !WASM_FILTER.read().expect("WASM_FILTER lock poisoned").is_enabled(&a.metadata) {
+				return tracing::Span::none()
+			}
+
+			let follows_from = a.follows_from;
 			let name = std::str::from_utf8(&a.metadata.name).unwrap_or_default();
 			let target = std::str::from_utf8(&a.metadata.target).unwrap_or_default();
 			let file = std::str::from_utf8(&a.metadata.file).unwrap_or_default();
 			let line = a.metadata.line;
 			let module_path = std::str::from_utf8(&a.metadata.module_path).unwrap_or_default();
-			let params = a.fields;
+			// Render through `to_json` rather than `Debug`, so any
+			// `EncodedValueDecoder` registered for a field name is applied
+			// here too, not just when a caller explicitly asks for JSON.
+			let params = a.fields.to_json();
 			let metadata : &tracing_core::metadata::Metadata<'static> = (&a.metadata).into();
 
-			tracing::span::Span::child_of(
+			let span = tracing::span::Span::child_of(
 				a.parent_id.map(|i|tracing_core::span::Id::from_u64(i)),
 				&metadata,
-				&tracing::valueset!{ metadata.fields(), target, name, file, line, module_path, ?params }
-			)
+				&tracing::valueset!{ metadata.fields(), target, name, file, line, module_path, %params }
+			);
+
+			// The subscriber is the authority on which ids are still live,
+			// so every id is forwarded as-is; how it treats ids it doesn't
+			// recognise is up to the subscriber.
+			for id in follows_from {
+				span.follows_from(tracing_core::span::Id::from_u64(id));
+			}
+
+			span
+		}
+	}
+
+	/// Everything needed to re-record values onto a span after it was
+	/// opened: the `Span` handle itself (for its id and metadata) plus the
+	/// string pieces the original `WasmMetadata` bytes decoded to, since
+	/// `record_values` has to rebuild the same generic field set.
+	struct OpenSpan {
+		span: tracing::Span,
+		name: std::string::String,
+		target: std::string::String,
+		file: std::string::String,
+		line: u32,
+		module_path: std::string::String,
+	}
+
+	lazy_static::lazy_static! {
+		/// Wasm-managed spans that have been opened but not yet closed,
+		/// keyed by the `u64` value of the `tracing_core` span id the
+		/// subscriber assigned them – the same value the wasm side threads
+		/// through as `parent_id` for children, and hands back to
+		/// `enter_span`/`record_values`/`exit_span`/`close_span`.
+		static ref OPEN_SPANS: RwLock<std::collections::HashMap<u64, OpenSpan>> =
+			RwLock::new(std::collections::HashMap::new());
+	}
+
+	impl crate::WasmEntryAttributes {
+		/// Turn this entry into a `tracing::Span` and open it for
+		/// wasm-managed lifetime tracking, returning the id the wasm side
+		/// should hand back to `enter_span`/`record_values`/`exit_span`/
+		/// `close_span`. Returns `None` if the span was filtered out, or
+		/// wasn't assigned an id by the current subscriber.
+		pub fn open_span(self) -> Option<u64> {
+			// `self` is consumed by `Into<Span>` below, so snapshot the
+			// decoded strings first – `record_values` needs them later to
+			// rebuild the same generic field set.
+			let name = std::str::from_utf8(&self.metadata.name).unwrap_or_default().to_string();
+			let target = std::str::from_utf8(&self.metadata.target).unwrap_or_default().to_string();
+			let file = std::str::from_utf8(&self.metadata.file).unwrap_or_default().to_string();
+			let module_path = std::str::from_utf8(&self.metadata.module_path).unwrap_or_default().to_string();
+			let line = self.metadata.line;
+
+			let span: tracing::Span = self.into();
+			let id = span.id()?.into_u64();
+			OPEN_SPANS.write().expect("OPEN_SPANS lock poisoned").insert(
+				id,
+				OpenSpan { span, name, target, file, line, module_path },
+			);
+			Some(id)
+		}
+	}
+
+	/// Enter the wasm-managed span `id`, pushing it onto the current
+	/// thread's span stack until a matching `exit_span`. Unknown ids are
+	/// silently ignored.
+	pub fn enter_span(id: u64) {
+		let spans = OPEN_SPANS.read().expect("OPEN_SPANS lock poisoned");
+		if spans.contains_key(&id) {
+			tracing_core::dispatcher::get_default(|dispatch| {
+				dispatch.enter(&tracing_core::span::Id::from_u64(id));
+			});
+		}
+	}
+
+	/// Exit the wasm-managed span `id`, popping it back off the current
+	/// thread's span stack. Unknown ids are silently ignored.
+	pub fn exit_span(id: u64) {
+		let spans = OPEN_SPANS.read().expect("OPEN_SPANS lock poisoned");
+		if spans.contains_key(&id) {
+			tracing_core::dispatcher::get_default(|dispatch| {
+				dispatch.exit(&tracing_core::span::Id::from_u64(id));
+			});
 		}
 	}
 
+	/// Record additional field values onto the still-open wasm-managed
+	/// span `id`. Unknown ids are silently ignored.
+	pub fn record_values(id: u64, values: crate::WasmValuesSet) {
+		let spans = OPEN_SPANS.read().expect("OPEN_SPANS lock poisoned");
+		if let Some(open) = spans.get(&id) {
+			let metadata = match open.span.metadata() {
+				Some(metadata) => metadata,
+				None => return,
+			};
+			let (name, target, file, module_path) =
+				(open.name.as_str(), open.target.as_str(), open.file.as_str(), open.module_path.as_str());
+			let line = open.line;
+			// Render through `to_json` rather than `Debug`, so any
+			// `EncodedValueDecoder` registered for a field name is applied
+			// here too, not just when a caller explicitly asks for JSON.
+			let params = values.to_json();
+
+			tracing_core::dispatcher::get_default(|dispatch| {
+				dispatch.record(
+					&tracing_core::span::Id::from_u64(id),
+					&tracing_core::span::Record::new(
+						&tracing::valueset!{ metadata.fields(), target, name, file, line, module_path, %params }
+					),
+				);
+			});
+		}
+	}
+
+	/// Permanently close the wasm-managed span `id`, allowing the
+	/// subscriber to free any resources tied to it. Unknown ids are
+	/// silently ignored.
+	pub fn close_span(id: u64) {
+		OPEN_SPANS.write().expect("OPEN_SPANS lock poisoned").remove(&id);
+	}
+
 	impl crate::WasmEntryAttributes {
 		/// convert the given Attributes to an event and emit it using `tracing_core`.
 		pub fn emit(self: crate::WasmEntryAttributes) {
+			if !WASM_FILTER.read().expect("WASM_FILTER lock poisoned").is_enabled(&self.metadata) {
+				return
+			}
+
 			let name = std::str::from_utf8(&self.metadata.name).unwrap_or_default();
 			let target = std::str::from_utf8(&self.metadata.target).unwrap_or_default();
 			let file = std::str::from_utf8(&self.metadata.file).unwrap_or_default();
 			let line = self.metadata.line;
 			let module_path = std::str::from_utf8(&self.metadata.module_path).unwrap_or_default();
-			let params = self.fields;
+			// Render through `to_json` rather than `Debug`, so any
+			// `EncodedValueDecoder` registered for a field name is applied
+			// here too, not just when a caller explicitly asks for JSON.
+			let params = self.fields.to_json();
 			let metadata : &tracing_core::metadata::Metadata<'static> = (&self.metadata).into();
 
 			tracing_core::Event::child_of(
 				self.parent_id.map(|i|tracing_core::span::Id::from_u64(i)),
 				&metadata,
-				&tracing::valueset!{ metadata.fields(), target, name, file, line, module_path, ?params }
+				&tracing::valueset!{ metadata.fields(), target, name, file, line, module_path, %params }
 			)
 		}
 	}
+
+	#[cfg(test)]
+	mod callsite_tests {
+		use super::*;
+
+		// `WasmCallsite::is_enabled` dispatches through the shared global
+		// statics above, and registering/rebuilding a subscriber touches all
+		// of them at once – serialise the tests that exercise it so they
+		// don't observe each other's `set_interest` calls.
+		lazy_static::lazy_static! {
+			static ref TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+		}
+
+		#[test]
+		fn fresh_callsite_starts_as_sometimes_and_asks_the_dispatcher() {
+			// A freshly constructed instance never touches the global
+			// statics, so this doesn't need `TEST_LOCK`.
+			static METADATA: tracing_core::Metadata<'static> = tracing::Metadata::new(
+				"callsite_tests", "callsite_tests", tracing::Level::INFO, None, None, None,
+				tracing_core::field::FieldSet::new(&[], tracing_core::identify_callsite!(&CALLSITE)),
+				tracing_core::metadata::Kind::EVENT,
+			);
+			static CALLSITE: WasmCallsite = WasmCallsite::new(&METADATA);
+
+			// No subscriber has been told about this callsite, so the cache
+			// is still `sometimes` and it falls back to asking the current
+			// (no-op) default dispatcher, which reports nothing enabled.
+			assert!(!CALLSITE.is_enabled_cached());
+		}
+
+		#[test]
+		fn cached_never_and_always_skip_asking_the_dispatcher() {
+			static METADATA: tracing_core::Metadata<'static> = tracing::Metadata::new(
+				"callsite_tests", "callsite_tests", tracing::Level::INFO, None, None, None,
+				tracing_core::field::FieldSet::new(&[], tracing_core::identify_callsite!(&CALLSITE)),
+				tracing_core::metadata::Kind::EVENT,
+			);
+			static CALLSITE: WasmCallsite = WasmCallsite::new(&METADATA);
+
+			callsite::Callsite::set_interest(&CALLSITE, tracing_core::Interest::never());
+			assert!(!CALLSITE.is_enabled_cached());
+
+			callsite::Callsite::set_interest(&CALLSITE, tracing_core::Interest::always());
+			assert!(CALLSITE.is_enabled_cached());
+		}
+
+		#[test]
+		fn is_enabled_dispatches_to_the_span_or_event_callsite_for_the_level() {
+			let _guard = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+			// Register first: `is_enabled` registers on its first call, and
+			// registering resets each callsite's interest.
+			register_callsites();
+
+			callsite::Callsite::set_interest(callsite_for(&crate::WasmLevel::WARN, true), tracing_core::Interest::always());
+			callsite::Callsite::set_interest(callsite_for(&crate::WasmLevel::WARN, false), tracing_core::Interest::never());
+
+			assert!(WasmCallsite::is_enabled(&crate::WasmLevel::WARN, true));
+			assert!(!WasmCallsite::is_enabled(&crate::WasmLevel::WARN, false));
+		}
+	}
 }
 
 #[cfg(feature = "std")]
-pub use std_features::*;
\ No newline at end of file
+pub use std_features::*;
+
+#[cfg(all(test, feature = "std"))]
+mod span_lifecycle_tests {
+	use super::*;
+	use std::sync::{Arc, Mutex};
+	use std::sync::atomic::{AtomicU64, Ordering};
+
+	fn span_attrs(is_span: bool) -> WasmEntryAttributes {
+		WasmEntryAttributes {
+			parent_id: None,
+			metadata: WasmMetadata {
+				name: b"span_lifecycle_tests".to_vec(),
+				target: b"span_lifecycle_tests".to_vec(),
+				level: WasmLevel::INFO,
+				file: b"file.rs".to_vec(),
+				line: 1,
+				module_path: b"module".to_vec(),
+				is_span,
+				fields: WasmFields::empty(),
+			},
+			fields: WasmValuesSet::empty(),
+			follows_from: Vec::new(),
+		}
+	}
+
+	#[test]
+	fn is_span_selects_the_span_or_event_metadata_kind() {
+		let span_metadata: &'static tracing_core::Metadata<'static> = (&span_attrs(true).metadata).into();
+		assert!(span_metadata.is_span());
+
+		let event_metadata: &'static tracing_core::Metadata<'static> = (&span_attrs(false).metadata).into();
+		assert!(event_metadata.is_event());
+	}
+
+	/// A `Subscriber` that always enables everything and records the order
+	/// `enter`/`record`/`exit` are called in, so `enter_span`/`record_values`/
+	/// `exit_span`/`close_span` can be checked end to end without a real
+	/// logging backend.
+	struct RecordingSubscriber {
+		log: Arc<Mutex<std::vec::Vec<std::string::String>>>,
+		next_id: AtomicU64,
+	}
+
+	impl RecordingSubscriber {
+		fn new() -> (Self, Arc<Mutex<std::vec::Vec<std::string::String>>>) {
+			let log = Arc::new(Mutex::new(std::vec::Vec::new()));
+			(Self { log: log.clone(), next_id: AtomicU64::new(1) }, log)
+		}
+	}
+
+	impl tracing_core::Subscriber for RecordingSubscriber {
+		fn enabled(&self, _metadata: &tracing_core::Metadata<'_>) -> bool {
+			true
+		}
+
+		fn new_span(&self, _span: &tracing_core::span::Attributes<'_>) -> tracing_core::span::Id {
+			tracing_core::span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed))
+		}
+
+		fn record(&self, span: &tracing_core::span::Id, _values: &tracing_core::span::Record<'_>) {
+			self.log.lock().unwrap().push(std::format!("record({})", span.clone().into_u64()));
+		}
+
+		fn record_follows_from(&self, span: &tracing_core::span::Id, follows: &tracing_core::span::Id) {
+			self.log.lock().unwrap().push(std::format!(
+				"follows_from({}, {})", span.clone().into_u64(), follows.clone().into_u64(),
+			));
+		}
+
+		fn event(&self, _event: &tracing_core::Event<'_>) {}
+
+		fn enter(&self, span: &tracing_core::span::Id) {
+			self.log.lock().unwrap().push(std::format!("enter({})", span.clone().into_u64()));
+		}
+
+		fn exit(&self, span: &tracing_core::span::Id) {
+			self.log.lock().unwrap().push(std::format!("exit({})", span.clone().into_u64()));
+		}
+	}
+
+	#[test]
+	fn open_enter_record_exit_close_are_forwarded_in_order() {
+		let (subscriber, log) = RecordingSubscriber::new();
+		let _dispatch_guard = tracing_core::dispatcher::set_default(&tracing_core::Dispatch::new(subscriber));
+
+		let id = span_attrs(true).open_span().expect("span is enabled and gets an id from the subscriber");
+		enter_span(id);
+		record_values(id, std::vec![(WasmFieldName::from("answer"), Some(WasmValue::U32(42)))].into());
+		exit_span(id);
+		close_span(id);
+
+		assert_eq!(
+			*log.lock().unwrap(),
+			std::vec![
+				std::format!("enter({})", id),
+				std::format!("record({})", id),
+				std::format!("exit({})", id),
+			],
+		);
+
+		// Once closed, the id is no longer tracked – further enter/exit
+		// calls for it are silently ignored rather than forwarded again.
+		log.lock().unwrap().clear();
+		enter_span(id);
+		exit_span(id);
+		assert!(log.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn follows_from_is_forwarded_to_the_subscriber_and_unknown_ids_do_not_fail_the_span() {
+		let (subscriber, log) = RecordingSubscriber::new();
+		let _dispatch_guard = tracing_core::dispatcher::set_default(&tracing_core::Dispatch::new(subscriber));
+
+		let first = span_attrs(true).open_span().expect("span is enabled and gets an id from the subscriber");
+
+		let mut second = span_attrs(true);
+		second.follows_from = std::vec![first, 0xdead_beef];
+		let second = second.open_span().expect("span is enabled and gets an id from the subscriber");
+
+		assert!(
+			log.lock().unwrap().contains(&std::format!("follows_from({}, {})", second, first)),
+			"expected {} to follow {}, got {:?}", second, first, log.lock().unwrap(),
+		);
+		// the unknown id 0xdead_beef is forwarded too, but doesn't stop
+		// `open_span` from opening the span and returning its id.
+	}
+}
+
+#[cfg(test)]
+mod filter_tests {
+	use super::*;
+
+	fn metadata(target: &str, level: WasmLevel) -> WasmMetadata {
+		WasmMetadata {
+			name: b"name".to_vec(),
+			target: target.as_bytes().to_vec(),
+			level,
+			file: b"file.rs".to_vec(),
+			line: 1,
+			module_path: b"module".to_vec(),
+			is_span: false,
+			fields: WasmFields::empty(),
+		}
+	}
+
+	#[test]
+	fn no_directives_enables_everything() {
+		let filter = WasmFilter::from_str("");
+		assert!(filter.is_enabled(&metadata("anything", WasmLevel::TRACE)));
+	}
+
+	#[test]
+	fn bare_level_sets_the_default() {
+		let filter = WasmFilter::from_str("warn");
+		assert!(filter.is_enabled(&metadata("anything", WasmLevel::WARN)));
+		assert!(filter.is_enabled(&metadata("anything", WasmLevel::ERROR)));
+		assert!(!filter.is_enabled(&metadata("anything", WasmLevel::INFO)));
+	}
+
+	#[test]
+	fn target_directive_overrides_the_default_for_matching_targets() {
+		let filter = WasmFilter::from_str("runtime=debug,sp_io=trace,info");
+		assert!(filter.is_enabled(&metadata("runtime", WasmLevel::DEBUG)));
+		assert!(!filter.is_enabled(&metadata("runtime", WasmLevel::TRACE)));
+		assert!(filter.is_enabled(&metadata("sp_io", WasmLevel::TRACE)));
+		assert!(filter.is_enabled(&metadata("other", WasmLevel::INFO)));
+		assert!(!filter.is_enabled(&metadata("other", WasmLevel::DEBUG)));
+	}
+
+	#[test]
+	fn longest_matching_prefix_wins() {
+		let filter = WasmFilter::from_str("runtime=warn,runtime::pallet=trace");
+		assert!(filter.is_enabled(&metadata("runtime::pallet", WasmLevel::TRACE)));
+		assert!(!filter.is_enabled(&metadata("runtime::other", WasmLevel::DEBUG)));
+	}
+
+	#[test]
+	fn last_bare_default_wins_on_repetition() {
+		let filter = WasmFilter::from_str("debug,warn");
+		assert!(filter.is_enabled(&metadata("anything", WasmLevel::WARN)));
+		assert!(!filter.is_enabled(&metadata("anything", WasmLevel::DEBUG)));
+	}
+
+	#[test]
+	fn empty_and_garbage_directives_are_skipped() {
+		let filter = WasmFilter::from_str(" , runtime=notalevel , =debug ,warn, ");
+		// `runtime=notalevel` and `=debug` don't parse, only the bare
+		// `warn` default should have taken effect.
+		assert!(filter.is_enabled(&metadata("runtime", WasmLevel::WARN)));
+		assert!(!filter.is_enabled(&metadata("runtime", WasmLevel::DEBUG)));
+	}
+
+	#[test]
+	fn severity_ordering_is_error_highest_trace_lowest() {
+		assert!(WasmLevel::ERROR.severity() < WasmLevel::WARN.severity());
+		assert!(WasmLevel::WARN.severity() < WasmLevel::INFO.severity());
+		assert!(WasmLevel::INFO.severity() < WasmLevel::DEBUG.severity());
+		assert!(WasmLevel::DEBUG.severity() < WasmLevel::TRACE.severity());
+	}
+}
+
+#[cfg(all(test, feature = "std"))]
+mod encoded_value_decoder_tests {
+	use super::*;
+
+	#[test]
+	fn to_hex_formats_as_0x_prefixed_lowercase() {
+		assert_eq!(to_hex(&[0xde, 0xad, 0xbe, 0xef]), "0xdeadbeef");
+		assert_eq!(to_hex(&[]), "0x");
+	}
+
+	#[test]
+	fn encoded_without_decoder_falls_back_to_hex() {
+		let value = WasmValue::Encoded(std::vec![0xca, 0xfe]);
+		assert_eq!(
+			value.to_json("encoded_value_decoder_tests::no_decoder"),
+			serde_json::json!({ "scale_hex": "0xcafe" }),
+		);
+	}
+
+	#[test]
+	fn encoded_uses_the_decoder_registered_for_its_field_name() {
+		let field = "encoded_value_decoder_tests::account_id";
+		register_encoded_value_decoder(field, |bytes| {
+			Some(std::format!("Account#{}", bytes.len()))
+		});
+
+		let value = WasmValue::Encoded(std::vec![1, 2, 3]);
+		assert_eq!(value.to_json(field), serde_json::json!("Account#3"));
+	}
+
+	#[test]
+	fn decoder_returning_none_falls_back_to_hex() {
+		let field = "encoded_value_decoder_tests::always_none";
+		register_encoded_value_decoder(field, |_bytes| None);
+
+		let value = WasmValue::Encoded(std::vec![0xff]);
+		assert_eq!(value.to_json(field), serde_json::json!({ "scale_hex": "0xff" }));
+	}
+}
+
+#[cfg(all(test, feature = "std"))]
+mod json_rendering_tests {
+	use super::*;
+
+	#[test]
+	fn scalar_and_string_values_map_to_their_natural_json_type() {
+		assert_eq!(WasmValue::U8(7).to_json("f"), serde_json::json!(7));
+		assert_eq!(WasmValue::I8(-7).to_json("f"), serde_json::json!(-7));
+		assert_eq!(WasmValue::U32(42).to_json("f"), serde_json::json!(42));
+		assert_eq!(WasmValue::I32(-42).to_json("f"), serde_json::json!(-42));
+		assert_eq!(WasmValue::I64(-1).to_json("f"), serde_json::json!(-1));
+		assert_eq!(WasmValue::U64(1).to_json("f"), serde_json::json!(1));
+		assert_eq!(WasmValue::Bool(true).to_json("f"), serde_json::json!(true));
+		assert_eq!(WasmValue::Str(b"hello".to_vec()).to_json("f"), serde_json::json!("hello"));
+		assert_eq!(WasmValue::Formatted(b"formatted".to_vec()).to_json("f"), serde_json::json!("formatted"));
+	}
+
+	#[test]
+	fn values_set_renders_fields_with_none_as_null() {
+		let set: WasmValuesSet = std::vec![
+			(WasmFieldName::from("present"), Some(WasmValue::U8(1))),
+			(WasmFieldName::from("absent"), None),
+		].into();
+
+		let rendered = set.to_json_value();
+		assert_eq!(rendered["present"], serde_json::json!(1));
+		assert_eq!(rendered["absent"], serde_json::Value::Null);
+	}
+
+	#[test]
+	fn entry_attributes_to_json_carries_metadata_and_nested_fields() {
+		let attrs = WasmEntryAttributes {
+			parent_id: None,
+			metadata: WasmMetadata {
+				name: b"my_span".to_vec(),
+				target: b"my_target".to_vec(),
+				level: WasmLevel::INFO,
+				file: b"file.rs".to_vec(),
+				line: 42,
+				module_path: b"my_module".to_vec(),
+				is_span: true,
+				fields: WasmFields::empty(),
+			},
+			fields: std::vec![(WasmFieldName::from("answer"), Some(WasmValue::U32(42)))].into(),
+			follows_from: Vec::new(),
+		};
+
+		let rendered: serde_json::Value = serde_json::from_str(&attrs.to_json()).unwrap();
+		assert_eq!(rendered["name"], serde_json::json!("my_span"));
+		assert_eq!(rendered["target"], serde_json::json!("my_target"));
+		assert_eq!(rendered["level"], serde_json::json!("INFO"));
+		assert_eq!(rendered["line"], serde_json::json!(42));
+		assert_eq!(rendered["module_path"], serde_json::json!("my_module"));
+		assert_eq!(rendered["is_span"], serde_json::json!(true));
+		assert_eq!(rendered["fields"]["answer"], serde_json::json!(42));
+	}
+}